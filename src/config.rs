@@ -0,0 +1,115 @@
+//! Persistent, declarative defaults for the CLI, loaded from a TOML file
+//! (by default `~/.config/zsh-history-cleaner/config.toml`) so users don't
+//! have to repeat the same flags on every invocation. Explicit CLI flags
+//! always take precedence over values loaded from this file.
+
+use crate::errors::HistoryError;
+use crate::history::DedupMode;
+use expand_tilde::expand_tilde;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Where [`Config::open`] looks when the CLI isn't given an explicit `--config` path.
+pub const DEFAULT_CONFIG_PATH: &str = "~/.config/zsh-history-cleaner/config.toml";
+
+/// Defaults for the cleaning operations, deserialized from TOML. Every field
+/// has a sensible default so a partial (or absent) config file is valid.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Drop commands starting with a space before deduplicating, matching
+    /// zsh's `HIST_IGNORE_SPACE`. Equivalent to the CLI's `--ignore-space`.
+    pub ignore_space: bool,
+
+    /// The default dedup mode, used unless overridden by `--dedup-mode`.
+    pub dedup_mode: DedupMode,
+
+    /// Regex patterns merged into `--remove-matching`, mirroring zsh's
+    /// `HISTORY_IGNORE`. Equivalent to a standing `--patterns-file`.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            ignore_space: false,
+            dedup_mode: DedupMode::default(),
+            ignore_patterns: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads `path`, tilde-expanding it first. Returns [`Config::default`]
+    /// when the file doesn't exist, so a missing config is not an error.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, HistoryError> {
+        let expanded_path = expand_tilde(&path).expect("Failed to expand tilde in the file path");
+        let name = expanded_path.to_string_lossy().to_string();
+
+        if !expanded_path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = std::fs::read_to_string(&expanded_path)
+            .map_err(|e| HistoryError::IoError(name.clone(), e.to_string()))?;
+
+        toml::from_str(&content).map_err(|e| HistoryError::ConfigError(name, e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    #[test]
+    fn test_open_returns_default_when_file_is_absent() {
+        let config = Config::open("/nonexistent/zsh-history-cleaner/config.toml").unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_open_parses_a_full_config_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"
+            ignore_space = true
+            dedup_mode = "consecutive"
+            ignore_patterns = ["AWS_SECRET", "token=\\S+"]
+            "#
+        )
+        .unwrap();
+
+        let config = Config::open(file.path()).unwrap();
+
+        assert!(config.ignore_space);
+        assert_eq!(config.dedup_mode, DedupMode::Consecutive);
+        assert_eq!(
+            config.ignore_patterns,
+            vec!["AWS_SECRET".to_string(), "token=\\S+".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_open_defaults_missing_fields() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "ignore_space = true").unwrap();
+
+        let config = Config::open(file.path()).unwrap();
+
+        assert!(config.ignore_space);
+        assert_eq!(config.dedup_mode, DedupMode::default());
+        assert!(config.ignore_patterns.is_empty());
+    }
+
+    #[test]
+    fn test_open_rejects_invalid_toml() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "ignore_space = not-a-bool").unwrap();
+
+        let err = Config::open(file.path()).unwrap_err();
+        assert!(matches!(err, HistoryError::ConfigError(_, _)));
+    }
+}
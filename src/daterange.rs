@@ -0,0 +1,158 @@
+//! Parses human-friendly date expressions (`"yesterday"`, `"3 days ago"`,
+//! `"2024-01-01"`, an RFC-3339 timestamp, ...) into an absolute [`NaiveDate`],
+//! so date-range filters (see [`crate::history::History::filter_by_date_range`]
+//! and [`crate::history::History::scoped_by_natural_date_range`], used by
+//! `--analyze`) aren't limited to exact `YYYY-MM-DD` input.
+
+use crate::errors::HistoryError;
+use chrono::{DateTime, Duration, Local, Months, NaiveDate};
+
+/// Parses `input` into an absolute [`NaiveDate`], anchored to today
+/// (`Local::now()`). Accepts, in order:
+/// - `today`, `yesterday`, `last week`, `last month`
+/// - `N day(s)/week(s)/month(s) ago`
+/// - `YYYY-MM-DD`
+/// - an RFC-3339 timestamp (only its date part is kept)
+pub fn parse_natural_date(input: &str) -> Result<NaiveDate, HistoryError> {
+    resolve(input, Local::now().date_naive())
+}
+
+/// Does the actual parsing against a supplied `anchor` date, so relative
+/// expressions can be tested without depending on the current date.
+fn resolve(input: &str, anchor: NaiveDate) -> Result<NaiveDate, HistoryError> {
+    let trimmed = input.trim();
+    let lower = trimmed.to_lowercase();
+
+    match lower.as_str() {
+        "today" => return Ok(anchor),
+        "yesterday" => return Ok(anchor - Duration::days(1)),
+        "last week" => return Ok(anchor - Duration::weeks(1)),
+        "last month" => {
+            return anchor
+                .checked_sub_months(Months::new(1))
+                .ok_or_else(|| invalid_expression(input));
+        }
+        _ => {}
+    }
+
+    if let Some(date) = parse_relative_ago(&lower, anchor) {
+        return Ok(date);
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(dt.date_naive());
+    }
+
+    Err(invalid_expression(input))
+}
+
+fn invalid_expression(input: &str) -> HistoryError {
+    HistoryError::DateParseError(
+        input.to_string(),
+        "expected a date like '2024-01-01', an RFC-3339 timestamp, or an expression like \
+         'yesterday', 'last week', 'last month', or '3 days ago'"
+            .to_string(),
+    )
+}
+
+/// Parses `"<N> day(s)/week(s)/month(s) ago"`.
+fn parse_relative_ago(lower: &str, anchor: NaiveDate) -> Option<NaiveDate> {
+    let rest = lower.strip_suffix(" ago")?;
+    let mut parts = rest.split_whitespace();
+    let count: i64 = parts.next()?.parse().ok()?;
+    let unit = parts.next()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    match unit {
+        "day" | "days" => Some(anchor - Duration::days(count)),
+        "week" | "weeks" => Some(anchor - Duration::weeks(count)),
+        "month" | "months" => anchor.checked_sub_months(Months::new(u32::try_from(count).ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn anchor() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 15).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_named_relative_expressions() {
+        assert_eq!(resolve("today", anchor()).unwrap(), anchor());
+        assert_eq!(
+            resolve("yesterday", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 14).unwrap()
+        );
+        assert_eq!(
+            resolve("last week", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 8).unwrap()
+        );
+        assert_eq!(
+            resolve("last month", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 5, 15).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_is_case_insensitive_and_trims_whitespace() {
+        assert_eq!(resolve("  Yesterday  ", anchor()).unwrap(), resolve("yesterday", anchor()).unwrap());
+    }
+
+    #[test]
+    fn test_resolve_n_units_ago() {
+        assert_eq!(
+            resolve("3 days ago", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 12).unwrap()
+        );
+        assert_eq!(
+            resolve("1 day ago", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 14).unwrap()
+        );
+        assert_eq!(
+            resolve("2 weeks ago", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 1).unwrap()
+        );
+        assert_eq!(
+            resolve("2 months ago", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 4, 15).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_absolute_date() {
+        assert_eq!(
+            resolve("2024-01-01", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_rfc3339_timestamp_keeps_only_the_date() {
+        assert_eq!(
+            resolve("2024-01-01T13:45:00Z", anchor()).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_rejects_garbage() {
+        let err = resolve("whenever", anchor()).unwrap_err();
+        assert!(matches!(err, HistoryError::DateParseError(_, _)));
+    }
+
+    #[test]
+    fn test_parse_natural_date_uses_todays_date_as_anchor() {
+        // Just exercises the public entry point end-to-end.
+        assert_eq!(parse_natural_date("today").unwrap(), Local::now().date_naive());
+    }
+}
@@ -18,4 +18,13 @@ pub enum HistoryError {
 
     #[error("Error when backing up the history to '{0}': {1}.")]
     BackUpError(String, String),
+
+    #[error("Error when exporting to the SQLite database '{0}': {1}.")]
+    SqliteError(String, String),
+
+    #[error("Error when reading the config file '{0}': {1}.")]
+    ConfigError(String, String),
+
+    #[error("Failed to parse date expression '{0}': {1}.")]
+    DateParseError(String, String),
 }
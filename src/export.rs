@@ -0,0 +1,278 @@
+use crate::entry::HistoryEntry;
+use crate::errors::HistoryError;
+use rusqlite::Connection;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Output format for the `export` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Human-readable, one entry per line.
+    Plain,
+    /// `timestamp,duration_secs,command` rows.
+    Csv,
+    /// A JSON array of `{"timestamp", "duration_secs", "command"}` objects.
+    Json,
+    /// Newline-delimited JSON: one `{"timestamp", "duration_secs", "command"}`
+    /// object per line, so entries can be streamed into `jq` or similar tools
+    /// without buffering the whole array first.
+    Ndjson,
+}
+
+/// Writes `entries` out in the given `format`, without touching the source history file.
+pub fn write_entries<W: Write>(
+    entries: &[HistoryEntry],
+    writer: &mut W,
+    format: ExportFormat,
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Plain => {
+            for entry in entries {
+                writeln!(writer, "{entry}")?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(writer, "timestamp,duration_secs,command")?;
+            for entry in entries {
+                writeln!(
+                    writer,
+                    "{},{},\"{}\"",
+                    entry
+                        .timestamp()
+                        .map_or_else(String::new, |timestamp| timestamp.to_string()),
+                    entry.duration().as_secs(),
+                    entry.command().replace('"', "\"\"")
+                )?;
+            }
+        }
+        ExportFormat::Json => {
+            writeln!(writer, "[")?;
+            for (i, entry) in entries.iter().enumerate() {
+                writeln!(
+                    writer,
+                    "  {}{}",
+                    entry_to_json(entry),
+                    if i + 1 < entries.len() { "," } else { "" }
+                )?;
+            }
+            writeln!(writer, "]")?;
+        }
+        ExportFormat::Ndjson => {
+            for entry in entries {
+                writeln!(writer, "{}", entry_to_json(entry))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Renders `entry` as a single-line `{"timestamp", "duration_secs", "command"}`
+/// JSON object, shared by [`ExportFormat::Json`] and [`ExportFormat::Ndjson`].
+fn entry_to_json(entry: &HistoryEntry) -> String {
+    format!(
+        "{{\"timestamp\": {}, \"duration_secs\": {}, \"command\": {}}}",
+        entry
+            .timestamp()
+            .map_or_else(|| "null".to_string(), |timestamp| timestamp.to_string()),
+        entry.duration().as_secs(),
+        crate::utils::to_json_string(entry.command()),
+    )
+}
+
+/// Exports `entries` into a SQLite database at `path` (à la zsh-histdb),
+/// creating a `commands` table and batching every row inside one transaction.
+/// `hostname`, `pwd`, `exit_status`, and `session` are left `NULL`: the Zsh
+/// extended history format can't recover them, but reserving the columns now
+/// keeps the schema stable for richer parsers added later. `start_time` is
+/// also `NULL` for plain (undated) entries.
+pub fn export_to_sqlite<P: AsRef<Path>>(
+    entries: &[HistoryEntry],
+    path: P,
+) -> Result<(), HistoryError> {
+    let path_name = path.as_ref().to_string_lossy().to_string();
+    let to_error = |e: rusqlite::Error| HistoryError::SqliteError(path_name.clone(), e.to_string());
+
+    let mut conn = Connection::open(path).map_err(to_error)?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS commands (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            start_time  INTEGER,
+            duration    INTEGER NOT NULL,
+            command     TEXT NOT NULL,
+            hostname    TEXT,
+            pwd         TEXT,
+            exit_status INTEGER,
+            session     INTEGER
+        )",
+        (),
+    )
+    .map_err(to_error)?;
+
+    let tx = conn.transaction().map_err(to_error)?;
+    {
+        let mut stmt = tx
+            .prepare(
+                "INSERT INTO commands (start_time, duration, command, hostname, pwd, exit_status, session)
+                 VALUES (?1, ?2, ?3, NULL, NULL, NULL, NULL)",
+            )
+            .map_err(to_error)?;
+
+        for entry in entries {
+            stmt.execute((entry.timestamp(), entry.duration().as_secs(), entry.command()))
+                .map_err(to_error)?;
+        }
+    }
+    tx.commit().map_err(to_error)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn sample_entries() -> Vec<HistoryEntry> {
+        vec![
+            HistoryEntry::try_from(": 1732577005:0;echo hello").unwrap(),
+            HistoryEntry::try_from(": 1732577037:2;echo \"world\"").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_write_entries_plain() {
+        let entries = sample_entries();
+        let mut out = Vec::new();
+        write_entries(&entries, &mut out, ExportFormat::Plain).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.lines().count(), 2);
+        assert!(out.contains("echo hello"));
+    }
+
+    #[test]
+    fn test_write_entries_csv_escapes_quotes() {
+        let entries = sample_entries();
+        let mut out = Vec::new();
+        write_entries(&entries, &mut out, ExportFormat::Csv).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let mut lines = out.lines();
+        assert_eq!(lines.next().unwrap(), "timestamp,duration_secs,command");
+        assert_eq!(lines.next().unwrap(), "1732577005,0,\"echo hello\"");
+        assert_eq!(lines.next().unwrap(), "1732577037,2,\"echo \"\"world\"\"\"");
+
+        let _ = Duration::from_secs(0); // keep `Duration` import meaningful if entries() changes shape
+    }
+
+    #[test]
+    fn test_write_entries_json() {
+        let entries = sample_entries();
+        let mut out = Vec::new();
+        write_entries(&entries, &mut out, ExportFormat::Json).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.starts_with("[\n"));
+        assert!(out.contains("\"timestamp\": 1732577005"));
+        assert!(out.trim_end().ends_with(']'));
+    }
+
+    #[test]
+    fn test_write_entries_ndjson_one_object_per_line() {
+        let entries = sample_entries();
+        let mut out = Vec::new();
+        write_entries(&entries, &mut out, ExportFormat::Ndjson).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            r#"{"timestamp": 1732577005, "duration_secs": 0, "command": "echo hello"}"#
+        );
+        assert!(lines[1].contains("\"timestamp\": 1732577037"));
+    }
+
+    // Debug-formatting (`{:?}`) would escape a bell character as `\u{7}`,
+    // which is not valid JSON; `entry_to_json` must produce `\u0007` instead.
+    #[test]
+    fn test_write_entries_ndjson_escapes_control_characters_as_valid_json() {
+        let bell = char::from_u32(0x0007).unwrap();
+        let command = format!("echo 'bell{}here'", bell);
+        let entries = vec![HistoryEntry::try_from(format!(": 1732577005:0;{command}")).unwrap()];
+        let mut out = Vec::new();
+        write_entries(&entries, &mut out, ExportFormat::Ndjson).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(
+            out.trim_end(),
+            r#"{"timestamp": 1732577005, "duration_secs": 0, "command": "echo 'bell\u0007here'"}"#
+        );
+    }
+
+    #[test]
+    fn test_export_to_sqlite_writes_enriched_rows() {
+        let entries = sample_entries();
+        let file = tempfile::NamedTempFile::new().unwrap();
+
+        export_to_sqlite(&entries, file.path()).unwrap();
+
+        let conn = rusqlite::Connection::open(file.path()).unwrap();
+        let mut stmt = conn
+            .prepare("SELECT start_time, duration, command, hostname FROM commands ORDER BY id")
+            .unwrap();
+        let rows: Vec<(i64, i64, String, Option<String>)> = stmt
+            .query_map((), |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], (1732577005, 0, "echo hello".to_string(), None));
+        assert_eq!(rows[1], (1732577037, 2, "echo \"world\"".to_string(), None));
+    }
+
+    #[test]
+    fn test_export_to_sqlite_stores_null_start_time_for_plain_entries() {
+        let entries = vec![HistoryEntry::try_from("echo undated").unwrap()];
+        let file = tempfile::NamedTempFile::new().unwrap();
+
+        export_to_sqlite(&entries, file.path()).unwrap();
+
+        let conn = rusqlite::Connection::open(file.path()).unwrap();
+        let start_time: Option<i64> = conn
+            .query_row("SELECT start_time FROM commands", (), |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(start_time, None);
+    }
+
+    #[test]
+    fn test_write_entries_csv_leaves_timestamp_blank_for_plain_entries() {
+        let entries = vec![HistoryEntry::try_from("echo undated").unwrap()];
+        let mut out = Vec::new();
+        write_entries(&entries, &mut out, ExportFormat::Csv).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let mut lines = out.lines();
+        lines.next(); // header
+        assert_eq!(lines.next().unwrap(), ",0,\"echo undated\"");
+    }
+
+    #[test]
+    fn test_export_to_sqlite_is_empty_for_no_entries() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+
+        export_to_sqlite(&[], file.path()).unwrap();
+
+        let conn = rusqlite::Connection::open(file.path()).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM commands", (), |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(count, 0);
+    }
+}
@@ -1,22 +1,140 @@
+use regex::{Error as RegexError, RegexSet};
+use std::io;
+use std::path::Path;
+
+/// Matches commands against a set of patterns, either as plain substrings
+/// (fast path, used by default) or as compiled regular expressions.
 pub struct Filter {
-    words: Vec<String>,
+    mode: FilterMode,
     ignore_case: bool,
 }
 
+enum FilterMode {
+    Literal(Vec<String>),
+    Regex(RegexSet),
+}
+
 impl Filter {
+    /// Builds a literal (substring) filter from `words`.
     pub fn new(words: &[String], ignore_case: bool) -> Self {
         Self {
-            words: words.to_vec(),
+            mode: FilterMode::Literal(words.to_vec()),
             ignore_case,
         }
     }
 
-    pub fn matches(&self, command: &str) -> bool {
-        if self.ignore_case {
-            let command = command.to_lowercase();
-            self.words.iter().any(|word| command.contains(&word.to_lowercase()))
+    /// Builds a filter backed by compiled regular expressions. A command
+    /// matches if any of `patterns` matches anywhere in it.
+    pub fn regex(patterns: &[String], ignore_case: bool) -> Result<Self, RegexError> {
+        let patterns = if ignore_case {
+            patterns
+                .iter()
+                .map(|pattern| format!("(?i){pattern}"))
+                .collect::<Vec<_>>()
         } else {
-            self.words.iter().any(|word| command.contains(word))
+            patterns.to_vec()
+        };
+
+        Ok(Self {
+            mode: FilterMode::Regex(RegexSet::new(patterns)?),
+            // Case-folding is already baked into the patterns above.
+            ignore_case: false,
+        })
+    }
+
+    /// Returns `true` if `command` matches any of the filter's patterns.
+    pub fn matches(&self, command: &str) -> bool {
+        match &self.mode {
+            FilterMode::Literal(words) => {
+                if self.ignore_case {
+                    let command = command.to_lowercase();
+                    words
+                        .iter()
+                        .any(|word| command.contains(&word.to_lowercase()))
+                } else {
+                    words.iter().any(|word| command.contains(word))
+                }
+            }
+            FilterMode::Regex(set) => set.is_match(command),
         }
     }
 }
+
+/// Reads a newline-delimited regex patterns file (one pattern per line),
+/// skipping blank lines and `#`-prefixed comments. Meant to be combined with
+/// patterns passed directly on the command line, e.g. for `--remove-matching`
+/// / `--redact-matching`.
+pub fn load_patterns_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)?;
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_literal_filter_matches_substring() {
+        let filter = Filter::new(&["AWS_SECRET".to_string()], false);
+        assert!(filter.matches("export AWS_SECRET=abc123"));
+        assert!(!filter.matches("export aws_secret=abc123"));
+    }
+
+    #[test]
+    fn test_literal_filter_ignores_case() {
+        let filter = Filter::new(&["aws_secret".to_string()], true);
+        assert!(filter.matches("export AWS_SECRET=abc123"));
+    }
+
+    #[test]
+    fn test_regex_filter_matches_any_pattern() {
+        let filter = Filter::regex(
+            &[r"curl https?://\S*token=\S+".to_string(), r"^docker".to_string()],
+            false,
+        )
+        .unwrap();
+
+        assert!(filter.matches("curl http://example.com?token=abc"));
+        assert!(filter.matches("docker ps -a"));
+        assert!(!filter.matches("ls -la"));
+    }
+
+    #[test]
+    fn test_regex_filter_ignore_case() {
+        let filter = Filter::regex(&["^DOCKER".to_string()], true).unwrap();
+        assert!(filter.matches("docker ps -a"));
+    }
+
+    #[test]
+    fn test_regex_filter_rejects_invalid_pattern() {
+        assert!(Filter::regex(&["(unclosed".to_string()], false).is_err());
+    }
+
+    #[test]
+    fn test_empty_regex_pattern_set_is_a_no_op() {
+        let filter = Filter::regex(&[], false).unwrap();
+        assert!(!filter.matches("rm -rf /"));
+        assert!(!filter.matches(""));
+    }
+
+    #[test]
+    fn test_load_patterns_file_skips_blank_lines_and_comments() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            "# a comment\n\n  ^docker  \nrm \\*\n   # another comment\n",
+        )
+        .unwrap();
+
+        let patterns = load_patterns_file(file.path()).unwrap();
+
+        assert_eq!(patterns, vec!["^docker".to_string(), "rm \\*".to_string()]);
+    }
+}
@@ -0,0 +1,186 @@
+use super::Importer;
+use crate::entry::HistoryEntry;
+use crate::errors::HistoryError;
+use crate::utils::read_line_lossy;
+use std::io::{BufReader, Read, Seek};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Imports plain Bash history files (`.bash_history`), optionally timestamped
+/// when `HISTTIMEFORMAT` is set: each command is then preceded by a `#<epoch>`
+/// comment line, and a command may span several lines until the next
+/// `#<epoch>` marker (or EOF) — those are joined with `\n` into one entry.
+/// Lines with no preceding timestamp marker have no timestamp and are each
+/// treated as a separate command, since there's nothing to group them by.
+pub struct BashImporter<R> {
+    entries: Vec<HistoryEntry>,
+    /// Number of lines that contained invalid UTF-8 and were repaired with
+    /// U+FFFD, when built via [`Self::new_with_options`] in lossy mode.
+    repaired_lines: usize,
+    _reader: PhantomData<R>,
+}
+
+impl<R: Read + Seek> BashImporter<R> {
+    /// Builds the importer, optionally tolerating invalid UTF-8 by replacing
+    /// bad sequences with U+FFFD instead of erroring (see `--lossy`).
+    pub fn new_with_options(reader: R, lossy: bool) -> Result<Self, HistoryError> {
+        let mut buf_reader = BufReader::new(reader);
+        let mut entries = Vec::new();
+        let mut pending_timestamp: Option<u64> = None;
+        let mut pending_lines: Vec<String> = Vec::new();
+        let mut repaired_lines = 0;
+        let mut line_number = 0;
+
+        while let Some((line, repaired)) = read_line_lossy(&mut buf_reader)
+            .map_err(|e| HistoryError::LineEncodingError(line_number.to_string(), e.to_string()))?
+        {
+            line_number += 1;
+
+            if repaired {
+                if !lossy {
+                    return Err(HistoryError::LineEncodingError(
+                        line_number.to_string(),
+                        "stream did not contain valid UTF-8".to_string(),
+                    ));
+                }
+                repaired_lines += 1;
+            }
+
+            if let Some(epoch) = line
+                .strip_prefix('#')
+                .and_then(|s| s.trim().parse::<u64>().ok())
+            {
+                flush_pending(&mut entries, pending_timestamp, &mut pending_lines);
+                pending_timestamp = Some(epoch);
+                continue;
+            }
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if pending_timestamp.is_some() {
+                pending_lines.push(line);
+            } else {
+                entries.push(HistoryEntry::new(line, None, Duration::from_secs(0)));
+            }
+        }
+
+        flush_pending(&mut entries, pending_timestamp, &mut pending_lines);
+
+        Ok(BashImporter {
+            entries,
+            repaired_lines,
+            _reader: PhantomData,
+        })
+    }
+
+    /// How many lines had invalid UTF-8 repaired with U+FFFD (always `0` in strict mode).
+    pub fn repaired_lines(&self) -> usize {
+        self.repaired_lines
+    }
+}
+
+impl<R: Read + Seek> Importer<R> for BashImporter<R> {
+    fn new(reader: R) -> Result<Self, HistoryError> {
+        Self::new_with_options(reader, false)
+    }
+
+    fn entries(&mut self) -> impl Iterator<Item = Result<HistoryEntry, HistoryError>> {
+        std::mem::take(&mut self.entries).into_iter().map(Ok)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Turns the lines collected for a `#<timestamp>` marker into one
+/// `HistoryEntry` (joined with `\n` when the command spanned several lines)
+/// and pushes it onto `entries`, then clears `pending_lines` for the next
+/// marker. No-op when nothing was collected (e.g. two markers in a row).
+fn flush_pending(
+    entries: &mut Vec<HistoryEntry>,
+    timestamp: Option<u64>,
+    pending_lines: &mut Vec<String>,
+) {
+    if !pending_lines.is_empty() {
+        entries.push(HistoryEntry::new(
+            pending_lines.join("\n"),
+            timestamp,
+            Duration::from_secs(0),
+        ));
+        pending_lines.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_bash_importer_parses_bare_commands() {
+        let raw = "ls -la\npwd\n";
+        let mut importer = BashImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command(), "ls -la");
+        assert_eq!(entries[0].timestamp(), None);
+        assert_eq!(entries[1].command(), "pwd");
+    }
+
+    #[test]
+    fn test_bash_importer_reads_histtimeformat_comments() {
+        let raw = "#1732577005\nls -la\n#1732577037\npwd\n";
+        let mut importer = BashImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command(), "ls -la");
+        assert_eq!(entries[0].timestamp(), Some(1732577005));
+        assert_eq!(entries[1].command(), "pwd");
+        assert_eq!(entries[1].timestamp(), Some(1732577037));
+    }
+
+    #[test]
+    fn test_bash_importer_groups_lines_until_next_marker_into_one_command() {
+        let raw = "#1732577005\necho one\necho two\n#1732577037\npwd\n";
+        let mut importer = BashImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command(), "echo one\necho two");
+        assert_eq!(entries[0].timestamp(), Some(1732577005));
+        assert_eq!(entries[1].command(), "pwd");
+    }
+
+    #[test]
+    fn test_bash_importer_strict_mode_rejects_invalid_utf8() {
+        let raw: &[u8] = b"ls \xFF\xFElater\n";
+        let err = BashImporter::new(Cursor::new(raw)).unwrap_err();
+        assert!(matches!(err, HistoryError::LineEncodingError(_, _)));
+    }
+
+    #[test]
+    fn test_bash_importer_lossy_mode_repairs_invalid_utf8() {
+        let raw: &[u8] = b"ls \xFF\xFElater\n";
+        let mut importer = BashImporter::new_with_options(Cursor::new(raw), true).unwrap();
+        assert_eq!(importer.repaired_lines(), 1);
+
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command(), "ls \u{FFFD}\u{FFFD}later");
+    }
+
+    #[test]
+    fn test_bash_importer_skips_blank_lines() {
+        let raw = "ls -la\n\n\npwd\n";
+        let mut importer = BashImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 2);
+    }
+}
@@ -0,0 +1,161 @@
+use super::Importer;
+use crate::entry::HistoryEntry;
+use crate::errors::HistoryError;
+use crate::utils::read_line_lossy;
+use std::io::{BufReader, Read, Seek};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Imports Fish's `fish_history` YAML-ish block format: each entry starts with
+/// `- cmd: <command>`, optionally followed by `  when: <epoch>` and `  paths:`
+/// lines (the latter are not commands and are ignored). Fish escapes embedded
+/// newlines within a command as a literal `\n`, which is unescaped back into
+/// a real newline here; the reverse of [`HistoryEntry::to_fish_block`].
+pub struct FishImporter<R> {
+    entries: Vec<HistoryEntry>,
+    /// Number of lines that contained invalid UTF-8 and were repaired with
+    /// U+FFFD, when built via [`Self::new_with_options`] in lossy mode.
+    repaired_lines: usize,
+    _reader: PhantomData<R>,
+}
+
+impl<R: Read + Seek> FishImporter<R> {
+    /// Builds the importer, optionally tolerating invalid UTF-8 by replacing
+    /// bad sequences with U+FFFD instead of erroring (see `--lossy`).
+    pub fn new_with_options(reader: R, lossy: bool) -> Result<Self, HistoryError> {
+        let mut buf_reader = BufReader::new(reader);
+        let mut entries = Vec::new();
+        let mut pending: Option<(String, Option<u64>)> = None;
+        let mut repaired_lines = 0;
+        let mut line_number = 0;
+
+        while let Some((line, repaired)) = read_line_lossy(&mut buf_reader)
+            .map_err(|e| HistoryError::LineEncodingError(line_number.to_string(), e.to_string()))?
+        {
+            line_number += 1;
+
+            if repaired {
+                if !lossy {
+                    return Err(HistoryError::LineEncodingError(
+                        line_number.to_string(),
+                        "stream did not contain valid UTF-8".to_string(),
+                    ));
+                }
+                repaired_lines += 1;
+            }
+
+            if let Some(cmd) = line.strip_prefix("- cmd: ") {
+                if let Some((command, timestamp)) = pending.take() {
+                    entries.push(HistoryEntry::new(command, timestamp, Duration::from_secs(0)));
+                }
+                pending = Some((cmd.replace("\\n", "\n"), None));
+            } else if let Some(when) = line.trim_start().strip_prefix("when: ") {
+                if let Some((_, timestamp)) = pending.as_mut()
+                    && let Ok(epoch) = when.trim().parse::<u64>()
+                {
+                    *timestamp = Some(epoch);
+                }
+            }
+            // `paths:` blocks and their `- <path>` children are metadata, not commands.
+        }
+
+        if let Some((command, timestamp)) = pending.take() {
+            entries.push(HistoryEntry::new(command, timestamp, Duration::from_secs(0)));
+        }
+
+        Ok(FishImporter {
+            entries,
+            repaired_lines,
+            _reader: PhantomData,
+        })
+    }
+
+    /// How many lines had invalid UTF-8 repaired with U+FFFD (always `0` in strict mode).
+    pub fn repaired_lines(&self) -> usize {
+        self.repaired_lines
+    }
+}
+
+impl<R: Read + Seek> Importer<R> for FishImporter<R> {
+    fn new(reader: R) -> Result<Self, HistoryError> {
+        Self::new_with_options(reader, false)
+    }
+
+    fn entries(&mut self) -> impl Iterator<Item = Result<HistoryEntry, HistoryError>> {
+        std::mem::take(&mut self.entries).into_iter().map(Ok)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_fish_importer_parses_cmd_and_when() {
+        let raw = "- cmd: ls -la\n  when: 1732577005\n- cmd: pwd\n  when: 1732577037\n";
+        let mut importer = FishImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command(), "ls -la");
+        assert_eq!(entries[0].timestamp(), Some(1732577005));
+        assert_eq!(entries[1].command(), "pwd");
+        assert_eq!(entries[1].timestamp(), Some(1732577037));
+    }
+
+    #[test]
+    fn test_fish_importer_handles_entry_without_when() {
+        let raw = "- cmd: ls -la\n- cmd: pwd\n  when: 1732577037\n";
+        let mut importer = FishImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command(), "ls -la");
+        assert_eq!(entries[0].timestamp(), None);
+        assert_eq!(entries[1].timestamp(), Some(1732577037));
+    }
+
+    #[test]
+    fn test_fish_importer_unescapes_embedded_newlines() {
+        let raw = "- cmd: echo one\\necho two\n  when: 1732577005\n";
+        let mut importer = FishImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command(), "echo one\necho two");
+    }
+
+    #[test]
+    fn test_fish_importer_strict_mode_rejects_invalid_utf8() {
+        let raw: &[u8] = b"- cmd: ls \xFF\xFElater\n";
+        let err = FishImporter::new(Cursor::new(raw)).unwrap_err();
+        assert!(matches!(err, HistoryError::LineEncodingError(_, _)));
+    }
+
+    #[test]
+    fn test_fish_importer_lossy_mode_repairs_invalid_utf8() {
+        let raw: &[u8] = b"- cmd: ls \xFF\xFElater\n";
+        let mut importer = FishImporter::new_with_options(Cursor::new(raw), true).unwrap();
+        assert_eq!(importer.repaired_lines(), 1);
+
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command(), "ls \u{FFFD}\u{FFFD}later");
+    }
+
+    #[test]
+    fn test_fish_importer_ignores_paths_block() {
+        let raw = "- cmd: git commit\n  when: 1732577005\n  paths:\n    - src/main.rs\n";
+        let mut importer = FishImporter::new(Cursor::new(raw)).unwrap();
+        let entries = importer.entries().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command(), "git commit");
+    }
+}
@@ -0,0 +1,232 @@
+//! Pluggable readers that convert shell-specific history files into the
+//! crate's internal [`HistoryEntry`] model, so `History` itself stays
+//! shell-agnostic.
+//!
+//! This is also where non-extended history lines are handled: [`ZshImporter`]
+//! parses both Zsh's extended format and bare (timestamp-less) lines via
+//! [`HistoryEntry::try_from`](crate::entry::HistoryEntry#impl-TryFrom<String>-for-HistoryEntry),
+//! while [`BashImporter`] and [`FishImporter`] cover plain Bash history (with
+//! optional `HISTTIMEFORMAT` comments) and Fish's YAML-ish blocks. [`Format::detect`]
+//! picks the right one automatically, so commands from any of the three shells
+//! can be cleaned or analyzed the same way.
+
+mod bash;
+mod fish;
+mod zsh;
+
+pub use bash::BashImporter;
+pub use fish::FishImporter;
+pub use zsh::ZshImporter;
+
+use crate::entry::HistoryEntry;
+use crate::errors::HistoryError;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+/// The shell a history file was produced by, or `Auto` to sniff it from content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Zsh,
+    Bash,
+    Fish,
+    Auto,
+}
+
+impl Format {
+    /// Sniffs the format of a history file from its first non-empty line.
+    /// Reads raw bytes rather than `read_to_string` so detection itself never
+    /// aborts on invalid UTF-8 - that's for the importer's strict/lossy modes
+    /// to decide, not this first sniffing pass - lossily decoding only the
+    /// first line, which is all the heuristic below looks at.
+    pub fn detect<R: Read>(reader: R) -> Result<Format, HistoryError> {
+        let mut buf_reader = BufReader::new(reader);
+        let mut buf = Vec::new();
+        buf_reader
+            .read_to_end(&mut buf)
+            .map_err(|e| HistoryError::IoError("<format detection>".to_string(), e.to_string()))?;
+
+        let text = String::from_utf8_lossy(&buf);
+        let first_line = text
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("");
+
+        let format = if first_line.starts_with("- cmd:") {
+            Format::Fish
+        } else if first_line.starts_with(": ") && first_line.contains(';') {
+            Format::Zsh
+        } else {
+            Format::Bash
+        };
+
+        Ok(format)
+    }
+}
+
+/// A reader that converts a shell-specific history file into [`HistoryEntry`] values.
+pub trait Importer<R: Read + Seek>: Sized {
+    /// Builds the importer from an already-opened reader.
+    fn new(reader: R) -> Result<Self, HistoryError>;
+
+    /// Iterates over the entries found in the underlying reader.
+    fn entries(&mut self) -> impl Iterator<Item = Result<HistoryEntry, HistoryError>>;
+
+    /// A cheap upper bound on the number of entries left to read, used for progress reporting.
+    fn size_hint(&self) -> usize;
+}
+
+/// Parses already-in-memory history data (e.g. fetched from somewhere other
+/// than the local filesystem) into [`HistoryEntry`] values. Pass `Format::Auto`
+/// to sniff the format from `bytes`' content, the same way [`import_entries`] does.
+pub fn parse_file(bytes: &[u8], format: Format) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let format = if format == Format::Auto {
+        Format::detect(Cursor::new(bytes))?
+    } else {
+        format
+    };
+
+    match format {
+        Format::Zsh => ZshImporter::new(Cursor::new(bytes))?.entries().collect(),
+        Format::Bash => BashImporter::new(Cursor::new(bytes))?.entries().collect(),
+        Format::Fish => FishImporter::new(Cursor::new(bytes))?.entries().collect(),
+        Format::Auto => unreachable!("Auto is resolved above"),
+    }
+}
+
+/// Reads every entry out of `path`, using `format` (must already be resolved, i.e. not `Auto`).
+pub fn import_entries<P: AsRef<Path>>(
+    path: P,
+    format: Format,
+) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let name = path.as_ref().to_string_lossy().to_string();
+
+    if format == Format::Auto {
+        let file = File::open(&path).map_err(|e| HistoryError::IoError(name, e.to_string()))?;
+        let resolved = Format::detect(file)?;
+        return import_entries(path, resolved);
+    }
+
+    let file = File::open(&path).map_err(|e| HistoryError::IoError(name, e.to_string()))?;
+
+    match format {
+        Format::Zsh => ZshImporter::new(file)?.entries().collect(),
+        Format::Bash => BashImporter::new(file)?.entries().collect(),
+        Format::Fish => FishImporter::new(file)?.entries().collect(),
+        Format::Auto => unreachable!("Auto is resolved above"),
+    }
+}
+
+/// Reads every entry out of `path` like [`import_entries`], but in lossy mode
+/// invalid UTF-8 is repaired with U+FFFD instead of aborting the run. Returns
+/// the entries plus how many lines were repaired.
+pub fn import_entries_lossy<P: AsRef<Path>>(
+    path: P,
+    format: Format,
+) -> Result<(Vec<HistoryEntry>, usize), HistoryError> {
+    let name = path.as_ref().to_string_lossy().to_string();
+
+    if format == Format::Auto {
+        let file = File::open(&path).map_err(|e| HistoryError::IoError(name, e.to_string()))?;
+        let resolved = Format::detect(file)?;
+        return import_entries_lossy(path, resolved);
+    }
+
+    let file = File::open(&path).map_err(|e| HistoryError::IoError(name, e.to_string()))?;
+
+    match format {
+        Format::Zsh => {
+            let mut importer = ZshImporter::new_with_options(file, true)?;
+            let entries = importer.entries().collect::<Result<Vec<_>, _>>()?;
+            Ok((entries, importer.repaired_lines()))
+        }
+        Format::Bash => {
+            let mut importer = BashImporter::new_with_options(file, true)?;
+            let entries = importer.entries().collect::<Result<Vec<_>, _>>()?;
+            Ok((entries, importer.repaired_lines()))
+        }
+        Format::Fish => {
+            let mut importer = FishImporter::new_with_options(file, true)?;
+            let entries = importer.entries().collect::<Result<Vec<_>, _>>()?;
+            Ok((entries, importer.repaired_lines()))
+        }
+        Format::Auto => unreachable!("Auto is resolved above"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use test_helpers::get_tmp_file;
+
+    #[test]
+    fn test_detect_recognizes_zsh_extended_format() {
+        let file = get_tmp_file(": 1732577005:0;ls -la");
+        let format = Format::detect(file.reopen().unwrap()).unwrap();
+        assert_eq!(format, Format::Zsh);
+    }
+
+    #[test]
+    fn test_detect_recognizes_fish_format() {
+        let file = get_tmp_file("- cmd: ls -la\n  when: 1732577005");
+        let format = Format::detect(file.reopen().unwrap()).unwrap();
+        assert_eq!(format, Format::Fish);
+    }
+
+    #[test]
+    fn test_detect_falls_back_to_bash_for_plain_commands() {
+        let file = get_tmp_file("ls -la");
+        let format = Format::detect(file.reopen().unwrap()).unwrap();
+        assert_eq!(format, Format::Bash);
+    }
+
+    // Invalid UTF-8 anywhere in the file must not abort detection itself -
+    // only `--lossy` vs strict mode, decided later by the importer, should
+    // determine whether that invalid UTF-8 is an error.
+    #[test]
+    fn test_detect_does_not_error_on_invalid_utf8() {
+        let mut raw = b": 1732577005:0;echo ".to_vec();
+        raw.extend_from_slice(b"\xFF\xFE");
+        let file = get_tmp_file_with_bytes(&raw);
+
+        let format = Format::detect(file.reopen().unwrap()).unwrap();
+        assert_eq!(format, Format::Zsh);
+    }
+
+    fn get_tmp_file_with_bytes(bytes: &[u8]) -> tempfile::NamedTempFile {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(bytes).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_parse_file_auto_detects_and_parses_in_memory_bash_history() {
+        let entries = parse_file(b"#1732577005\nls -la", Format::Auto).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command(), "ls -la");
+        assert_eq!(entries[0].timestamp(), Some(1732577005));
+    }
+
+    #[test]
+    fn test_import_entries_auto_detects_and_parses_bash_history() {
+        let file = get_tmp_file("#1732577005\nls -la");
+        let entries = import_entries(file.path(), Format::Auto).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command(), "ls -la");
+        assert_eq!(entries[0].timestamp(), Some(1732577005));
+    }
+
+    #[test]
+    fn test_import_entries_auto_detects_and_parses_fish_history() {
+        let file = get_tmp_file("- cmd: ls -la\n  when: 1732577005");
+        let entries = import_entries(file.path(), Format::Auto).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command(), "ls -la");
+        assert_eq!(entries[0].timestamp(), Some(1732577005));
+    }
+}
@@ -0,0 +1,68 @@
+use super::Importer;
+use crate::entry::HistoryEntry;
+use crate::errors::HistoryError;
+use crate::utils::count_lines;
+use crate::utils::zsh_line::ZshCommands;
+use std::io::{Read, Seek, SeekFrom};
+
+/// Imports Zsh extended-history files (`: <epoch>:<dur>;<cmd>`), decoding
+/// metafied bytes and reassembling backslash-continued multi-line commands
+/// via [`ZshCommands`], one command at a time.
+pub struct ZshImporter<R> {
+    commands: ZshCommands<R>,
+    /// Number of physical lines that contained invalid UTF-8 and were repaired
+    /// with U+FFFD, when built via [`Self::new_with_options`] in lossy mode.
+    repaired_lines: usize,
+    size_hint: usize,
+}
+
+impl<R: Read + Seek> ZshImporter<R> {
+    /// Builds the importer, optionally tolerating invalid UTF-8 by replacing
+    /// bad sequences with U+FFFD instead of erroring (see `--lossy`).
+    pub fn new_with_options(mut reader: R, lossy: bool) -> Result<Self, HistoryError> {
+        // A cheap pre-pass for the size hint: count newlines in the raw bytes,
+        // then rewind so the real, streaming read starts from the top.
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|e| HistoryError::IoError("<size hint>".to_string(), e.to_string()))?;
+        reader
+            .seek(SeekFrom::Start(0))
+            .map_err(|e| HistoryError::IoError("<size hint>".to_string(), e.to_string()))?;
+        let size_hint = count_lines(&bytes);
+        drop(bytes);
+
+        Ok(ZshImporter {
+            commands: ZshCommands::new(reader, lossy),
+            repaired_lines: 0,
+            size_hint,
+        })
+    }
+
+    /// How many lines had invalid UTF-8 repaired with U+FFFD (always `0` in strict mode).
+    pub fn repaired_lines(&self) -> usize {
+        self.repaired_lines
+    }
+}
+
+impl<R: Read + Seek> Importer<R> for ZshImporter<R> {
+    fn new(reader: R) -> Result<Self, HistoryError> {
+        Self::new_with_options(reader, false)
+    }
+
+    fn entries(&mut self) -> impl Iterator<Item = Result<HistoryEntry, HistoryError>> {
+        let repaired_lines = &mut self.repaired_lines;
+        (&mut self.commands).map(move |result| {
+            result.map(|(raw_command, repaired)| {
+                if repaired {
+                    *repaired_lines += 1;
+                }
+                HistoryEntry::try_from_raw_bytes(raw_command)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+}
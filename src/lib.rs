@@ -0,0 +1,10 @@
+pub mod config;
+pub mod daterange;
+pub mod entry;
+pub mod errors;
+pub mod export;
+pub mod filters;
+pub mod history;
+pub mod import;
+pub mod secrets;
+pub mod utils;
@@ -1,8 +1,14 @@
 use std::process::ExitCode;
 
 use chrono::NaiveDate;
-use clap::{ArgAction, Parser};
-use zsh_history_cleaner::history;
+use clap::{ArgAction, Parser, Subcommand};
+use regex::Regex;
+use zsh_history_cleaner::config::{self, Config};
+use zsh_history_cleaner::entry::HistoryEntry;
+use zsh_history_cleaner::export::{self, ExportFormat};
+use zsh_history_cleaner::filters::{self, Filter};
+use zsh_history_cleaner::history::{self, DedupMode, NormalizeMode, RecencyMode};
+use zsh_history_cleaner::import::Format;
 use zsh_history_cleaner::utils::TERMINAL_MAX_WIDTH;
 
 /// Clean your commands history by removing duplicate commands, commands between dates, etc...
@@ -19,6 +25,22 @@ struct Cli {
     #[arg(short = 'H', long, default_value = "~/.zsh_history")]
     history_file: String,
 
+    /// The shell format of the history file. `auto` sniffs it from the file content.
+    #[arg(short = 'F', long, value_enum, default_value = "zsh")]
+    format: Format,
+
+    /// Path to a TOML config file supplying defaults (ignore_space, dedup_mode,
+    /// ignore_patterns) so they don't have to be repeated on every invocation.
+    /// Explicit flags below always override values loaded from it. Defaults to
+    /// `~/.config/zsh-history-cleaner/config.toml`, silently unused if absent.
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Tolerate invalid UTF-8 in the history file by replacing bad sequences with
+    /// U+FFFD instead of aborting. Off by default so data corruption stays visible.
+    #[arg(long, visible_alias = "ignore-encoding-errors", action = ArgAction::SetTrue, default_value = "false")]
+    lossy: bool,
+
     /// [USE WITH CAUTION!!] Disable the history file backup. By default, a backup is written to '{history_file_path}.zhc_backup_{timestamp}'.
     #[arg(long, action = ArgAction::SetTrue, default_value = "false")]
     no_backup: bool,
@@ -27,6 +49,47 @@ struct Cli {
     #[arg(short, long, action = ArgAction::SetTrue, default_value = "false")]
     keep_duplicates: bool,
 
+    /// How to deduplicate commands: `global` drops any earlier/later repeat,
+    /// `consecutive` only drops a repeat that immediately follows its match
+    /// (zsh's HIST_IGNORE_DUPS). Defaults to the config file's `dedup_mode`,
+    /// or `global` if neither is set.
+    #[arg(long, value_enum)]
+    dedup_mode: Option<DedupMode>,
+
+    /// In `global` dedup mode, keep the first occurrence of each command
+    /// instead of the last.
+    #[arg(long, action = ArgAction::SetTrue, default_value = "false")]
+    keep_first_duplicate: bool,
+
+    /// Drop commands starting with a space before deduplicating (zsh's
+    /// HIST_IGNORE_SPACE). Also enabled by the config file's `ignore_space`.
+    #[arg(long, action = ArgAction::SetTrue, default_value = "false")]
+    ignore_space: bool,
+
+    /// Collapse near-duplicate commands that only differ by formatting or
+    /// trailing arguments (see --normalize-mode), keeping the most recent one.
+    #[arg(long, action = ArgAction::SetTrue, default_value = "false")]
+    remove_near_duplicates: bool,
+
+    /// How to normalize commands when looking for near-duplicates with --remove-near-duplicates.
+    #[arg(long, value_enum, default_value = "collapse-whitespace")]
+    normalize_mode: NormalizeMode,
+
+    /// Deduplicate by exact command string, comparing timestamps rather than
+    /// file position (so an out-of-order history file still dedups correctly).
+    #[arg(long, action = ArgAction::SetTrue, default_value = "false")]
+    dedup_by_recency: bool,
+
+    /// Which occurrence to keep when using --dedup-by-recency.
+    #[arg(long, value_enum, default_value = "keep-newest")]
+    recency_mode: RecencyMode,
+
+    /// Re-emit the surviving entries sorted oldest-to-newest by timestamp,
+    /// instead of preserving their original file order. Only applies with
+    /// --dedup-by-recency.
+    #[arg(long, action = ArgAction::SetTrue, default_value = "false")]
+    sort_by_recency: bool,
+
     /// Remove commands between the provided two dates (included): YYYY-MM-DD YYYY-MM-DD. The first date must be before or equal to the second date.
     /// Example: --remove-between 2023-01-01 2023-06-30
     #[arg(short, long, num_args = 2, value_names = ["START_DATE", "END_DATE"], value_parser = validate_date)]
@@ -36,6 +99,145 @@ struct Cli {
     /// No changes are made to the history file when this flag is used.
     #[arg(short, long)]
     analyze: bool,
+
+    /// Used with --analyze: print the analysis as a single-line JSON object
+    /// instead of the human-readable table, so it can be piped into `jq` or
+    /// another tool.
+    #[arg(long, requires = "analyze", action = ArgAction::SetTrue, default_value = "false")]
+    json: bool,
+
+    /// Used with --analyze: restrict it to commands on or after this date.
+    /// Accepts natural-language expressions ("yesterday", "3 days ago", "last
+    /// week") as well as YYYY-MM-DD. Requires --analyze so it can never
+    /// silently scope down (and on write, delete) the cleaning pipeline
+    /// itself - use the `export`/`export-sqlite` subcommands' own
+    /// --since/--until to restrict what's read out of the history file.
+    #[arg(long, requires = "analyze")]
+    since: Option<String>,
+
+    /// Used with --analyze: restrict it to commands on or before this date.
+    /// See --since for accepted formats and why --analyze is required.
+    #[arg(long, requires = "analyze")]
+    until: Option<String>,
+
+    /// Used with --analyze: only read the trailing N bytes of the history
+    /// file (via `History::from_file_tail`) instead of parsing it in full, so
+    /// analyzing an enormous history file runs in time proportional to this
+    /// window rather than the whole file. Only supported with `--format zsh`
+    /// (the default). Requires --analyze for the same reason --since/--until
+    /// do: reading a partial window and then running the cleaning pipeline
+    /// and `write()` over it would silently drop everything outside the
+    /// window from the history file.
+    #[arg(long, requires = "analyze")]
+    tail_bytes: Option<u64>,
+
+    /// Remove every command matching this regex (can be passed multiple times).
+    /// Aliased as --ignore for the common case of purging noisy commands
+    /// (`ls`, `cd`, `clear`, ...).
+    /// Example: --remove-matching 'AWS_SECRET' --remove-matching 'token=\S+'
+    #[arg(long, visible_alias = "ignore")]
+    remove_matching: Vec<String>,
+
+    /// Keep only commands matching this regex, removing everything else (can be passed multiple times).
+    /// Aliased as --keep-only for the common case of retaining just one tool's
+    /// history, e.g. --keep-only '^git ' --keep-only '^docker '.
+    #[arg(long, visible_alias = "keep-only", conflicts_with = "remove_matching")]
+    keep_matching: Vec<String>,
+
+    /// Redact commands matching this regex in place instead of removing them
+    /// (can be passed multiple times). Mirrors zsh's `HISTORY_IGNORE`, but
+    /// scrubs the command instead of dropping the whole entry.
+    #[arg(long)]
+    redact_matching: Vec<String>,
+
+    /// Replacement text used by --redact-matching.
+    #[arg(long, default_value = "*** REDACTED ***")]
+    redaction_placeholder: String,
+
+    /// A file of newline-delimited regex patterns (one per line, `#` for
+    /// comments) to add to --remove-matching/--keep-matching/--redact-matching,
+    /// whichever is in use.
+    #[arg(long)]
+    patterns_file: Option<String>,
+
+    /// Drop every command that looks like it contains a credential (AWS
+    /// keys, GitHub/Slack tokens, password assignments, PEM private keys,
+    /// ...) without ever printing the secret itself. Use --redact-matching
+    /// instead if you'd rather scrub a specific pattern than drop the entry.
+    #[arg(long, action = ArgAction::SetTrue, default_value = "false")]
+    remove_secrets: bool,
+
+    /// Keep only the last N entries, dropping the oldest ones.
+    #[arg(long)]
+    truncate_to: Option<usize>,
+
+    /// Drop entries older than N days. Entries with an invalid timestamp are kept.
+    #[arg(long)]
+    keep_within: Option<i64>,
+
+    /// Drop the oldest entries until the serialized history fits under this many bytes.
+    #[arg(long)]
+    truncate_to_bytes: Option<u64>,
+
+    /// Merge another shell's history file into the history file before cleaning.
+    /// Example: --merge-from ~/.bash_history --merge-format bash
+    #[arg(long)]
+    merge_from: Option<String>,
+
+    /// The shell format of the file passed to `--merge-from`.
+    #[arg(long, value_enum, default_value = "auto")]
+    merge_format: Format,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Export the history file to stdout, optionally filtered by date range or a regex.
+    /// The history file is never modified.
+    Export {
+        /// Only export commands executed on or after this date. Accepts
+        /// natural-language expressions ("yesterday", "3 days ago", "last
+        /// week") as well as YYYY-MM-DD.
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only export commands executed on or before this date. See --since
+        /// for accepted formats.
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Only export commands matching this regex
+        #[arg(long)]
+        regex: Option<String>,
+
+        /// The output format
+        #[arg(long, value_enum, default_value = "plain")]
+        format: ExportFormat,
+    },
+
+    /// Export the history file to a SQLite database (à la zsh-histdb) for
+    /// ad-hoc SQL analysis. The history file is never modified.
+    ExportSqlite {
+        /// Only export commands executed on or after this date. Accepts
+        /// natural-language expressions ("yesterday", "3 days ago", "last
+        /// week") as well as YYYY-MM-DD.
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only export commands executed on or before this date. See --since
+        /// for accepted formats.
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Only export commands matching this regex
+        #[arg(long)]
+        regex: Option<String>,
+
+        /// Path to the SQLite database file to create or append to
+        path: String,
+    },
 }
 
 impl Cli {
@@ -64,9 +266,102 @@ fn validate_date(date_str: &str) -> Result<NaiveDate, String> {
     })
 }
 
+/// Loads `history_file` and returns the entries matching the given date
+/// range and/or regex, in file order. Never touches the history file itself.
+/// `since`/`until` accept natural-language expressions, see
+/// [`history::History::filter_by_natural_date_range`].
+fn load_filtered_entries(
+    history_file: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+    regex: Option<&str>,
+) -> Result<Vec<HistoryEntry>, String> {
+    let history =
+        history::History::from_file(&history_file).map_err(|err| err.to_string())?;
+
+    let regex = regex
+        .map(Regex::new)
+        .transpose()
+        .map_err(|err| err.to_string())?;
+
+    Ok(history
+        .filter_by_natural_date_range(since, until)
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .filter(|entry| {
+            regex
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(entry.command()))
+        })
+        .collect())
+}
+
+/// Loads `history_file`, filters its entries by date range and/or regex, and
+/// writes them to stdout in `format`. Never touches the history file itself.
+fn run_export(
+    history_file: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+    regex: Option<&str>,
+    format: ExportFormat,
+) -> Result<(), String> {
+    let entries = load_filtered_entries(history_file, since, until, regex)?;
+
+    let stdout = std::io::stdout();
+    export::write_entries(&entries, &mut stdout.lock(), format).map_err(|err| err.to_string())
+}
+
+/// Loads `history_file`, filters its entries by date range and/or regex, and
+/// exports them into a SQLite database at `path`. Never touches the history
+/// file itself.
+fn run_export_sqlite(
+    history_file: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+    regex: Option<&str>,
+    path: &str,
+) -> Result<(), String> {
+    let entries = load_filtered_entries(history_file, since, until, regex)?;
+
+    export::export_to_sqlite(&entries, path).map_err(|err| err.to_string())
+}
+
 fn run(cli: Cli) -> Result<Option<String>, String> {
-    let mut history =
-        history::History::from_file(&cli.history_file).map_err(|err| err.to_string())?;
+    let config = Config::open(
+        cli.config
+            .as_deref()
+            .unwrap_or(config::DEFAULT_CONFIG_PATH),
+    )
+    .map_err(|err| err.to_string())?;
+
+    let mut history = if let Some(tail_bytes) = cli.tail_bytes {
+        if cli.format != Format::Zsh {
+            return Err("--tail-bytes only supports --format zsh.".to_string());
+        }
+        history::History::from_file_tail(&cli.history_file, tail_bytes)
+            .map_err(|err| err.to_string())?
+    } else if cli.lossy {
+        let (history, repaired_lines) =
+            history::History::from_file_lossy(&cli.history_file, cli.format)
+                .map_err(|err| err.to_string())?;
+        if repaired_lines > 0 {
+            println!(
+                "{} line(s) had invalid UTF-8 repaired with U+FFFD.",
+                repaired_lines
+            );
+        }
+        history
+    } else {
+        history::History::from_file_with_format(&cli.history_file, cli.format)
+            .map_err(|err| err.to_string())?
+    };
+
+    if let Some(merge_from) = &cli.merge_from {
+        let imported = history
+            .merge_from_file(merge_from, cli.merge_format)
+            .map_err(|err| err.to_string())?;
+        println!("{} entries merged from '{}'.", imported, merge_from);
+    }
 
     let should_backup = !cli.no_backup;
 
@@ -85,8 +380,18 @@ fn run(cli: Cli) -> Result<Option<String>, String> {
     }
 
     if cli.analyze {
+        if cli.since.is_some() || cli.until.is_some() {
+            history = history
+                .scoped_by_natural_date_range(cli.since.as_deref(), cli.until.as_deref())
+                .map_err(|err| err.to_string())?;
+        }
+
         let time_analysis = history.analyze_by_time();
-        println!("{}", time_analysis);
+        if cli.json {
+            println!("{}", time_analysis.to_json());
+        } else {
+            println!("{}", time_analysis);
+        }
         return Ok(None);
     }
 
@@ -94,16 +399,87 @@ fn run(cli: Cli) -> Result<Option<String>, String> {
 
     println!("{} entries in '{}'", history.size(), history.filename());
 
+    let dedup_mode = cli.dedup_mode.unwrap_or(config.dedup_mode);
+    let ignore_space = cli.ignore_space || config.ignore_space;
+
     if !cli.keep_duplicates {
-        let count = history.remove_duplicates();
+        let count =
+            history.remove_duplicates(dedup_mode, cli.keep_first_duplicate, ignore_space);
         println!("{} duplicate commands found.", count);
     }
 
+    if cli.remove_near_duplicates {
+        let count = history.remove_near_duplicates(cli.normalize_mode);
+        println!("{} near-duplicate commands collapsed.", count);
+    }
+
+    if cli.dedup_by_recency {
+        let count = history.dedup_by_recency(cli.recency_mode, cli.sort_by_recency);
+        println!("{} duplicate commands removed by --dedup-by-recency.", count);
+    }
+
     if let Some(dates) = cli.remove_between {
         history.remove_between_dates(&dates[0], &dates[1]);
     }
 
-    if history.size() == initial_size {
+    let patterns_from_file = cli
+        .patterns_file
+        .as_ref()
+        .map(filters::load_patterns_file)
+        .transpose()
+        .map_err(|err| err.to_string())?
+        .unwrap_or_default();
+
+    let mut remove_matching = cli.remove_matching;
+    remove_matching.extend(patterns_from_file.iter().cloned());
+    remove_matching.extend(config.ignore_patterns);
+
+    let mut keep_matching = cli.keep_matching;
+    keep_matching.extend(patterns_from_file.iter().cloned());
+
+    let mut redact_matching = cli.redact_matching;
+    redact_matching.extend(patterns_from_file);
+
+    if !remove_matching.is_empty() {
+        let filter = Filter::regex(&remove_matching, false).map_err(|err| err.to_string())?;
+        let count = history.remove_matching(&filter);
+        println!("{} commands removed by --remove-matching.", count);
+    }
+
+    if !keep_matching.is_empty() {
+        let filter = Filter::regex(&keep_matching, false).map_err(|err| err.to_string())?;
+        let count = history.keep_matching(&filter);
+        println!("{} commands removed by --keep-matching.", count);
+    }
+
+    let mut redacted_count = 0;
+    if !redact_matching.is_empty() {
+        let filter = Filter::regex(&redact_matching, false).map_err(|err| err.to_string())?;
+        redacted_count = history.redact_matching(&filter, &cli.redaction_placeholder);
+        println!("{} commands redacted by --redact-matching.", redacted_count);
+    }
+
+    if cli.remove_secrets {
+        let count = history.remove_secrets();
+        println!("{} commands removed for containing a likely secret.", count);
+    }
+
+    if let Some(days) = cli.keep_within {
+        let count = history.keep_within(days);
+        println!("{} entries older than {} day(s) removed.", count, days);
+    }
+
+    if let Some(max_entries) = cli.truncate_to {
+        let count = history.truncate_to(max_entries);
+        println!("{} entries removed to fit within {} entries.", count, max_entries);
+    }
+
+    if let Some(max_bytes) = cli.truncate_to_bytes {
+        let count = history.truncate_to_bytes(max_bytes);
+        println!("{} entries removed to fit within {} bytes.", count, max_bytes);
+    }
+
+    if history.size() == initial_size && redacted_count == 0 {
         println!("No changes were made to the history file.");
         return Ok(None);
     }
@@ -125,7 +501,37 @@ fn main() -> ExitCode {
         return ExitCode::FAILURE;
     }
 
-    if let Err(err) = run(cli) {
+    let result = match &cli.command {
+        Some(Commands::Export {
+            since,
+            until,
+            regex,
+            format,
+        }) => run_export(
+            &cli.history_file,
+            since.as_deref(),
+            until.as_deref(),
+            regex.as_deref(),
+            *format,
+        )
+        .map(|()| None),
+        Some(Commands::ExportSqlite {
+            since,
+            until,
+            regex,
+            path,
+        }) => run_export_sqlite(
+            &cli.history_file,
+            since.as_deref(),
+            until.as_deref(),
+            regex.as_deref(),
+            path,
+        )
+        .map(|()| None),
+        None => run(cli),
+    };
+
+    if let Err(err) = result {
         eprintln!("Error: {}", err);
         ExitCode::FAILURE
     } else {
@@ -1,49 +1,16 @@
-use crate::errors;
 use console::style;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-
-
-/// Reads a Zsh history file and processes its contents into a vector of complete commands.
-/// This function handles multiline commands (indicated by a trailing backslash `\`) by combining them into a single logical command.
-pub(crate) fn read_history_file<P: AsRef<Path>>(
-    filepath: &P,
-) -> Result<Vec<String>, errors::HistoryError> {
-    let mut commands = Vec::new();
-    let mut current_command = String::new();
-
-    let name = filepath.as_ref().to_string_lossy().to_string();
-
-    let file = File::open(filepath)
-        .map_err(|e| errors::HistoryError::IoError(name.clone(), e.to_string()))?;
-    let reader = BufReader::new(file);
-
-    for (counter, line) in reader.lines().enumerate() {
-        let line = line.map_err(|e| {
-            errors::HistoryError::LineEncodingError((counter + 1).to_string(), e.to_string())
-        })?;
-        let trimmed = line.trim_end(); // Trim trailing whitespace
-        if trimmed.ends_with('\\') {
-            // Remove the backslash and keep appending
-            current_command.push_str(trimmed);
-        } else {
-            if !current_command.is_empty() {
-                // Still appending a multi-line command
-                current_command.push('\n');
-            }
-            current_command.push_str(trimmed);
-
-            commands.push(current_command.clone());
-            current_command.clear();
-        }
-    }
 
-    if !current_command.is_empty() {
-        commands.push(current_command);
-    }
+pub(crate) mod zsh_line;
 
-    Ok(commands)
+/// Maximum width for terminal when displaying some things.
+pub const TERMINAL_MAX_WIDTH: u8 = 90;
+
+/// A cheap upper bound on the number of history entries in `bytes`, used to
+/// size progress bars before actually parsing anything. Counts newlines via
+/// `memchr` rather than parsing, so it's exact for single-line commands and
+/// an overestimate for multi-line ones.
+pub(crate) fn count_lines(bytes: &[u8]) -> usize {
+    memchr::memchr_iter(b'\n', bytes).count()
 }
 
 /// Helper function to truncate the text used for displaying the command and executables in table cells.
@@ -68,6 +35,59 @@ pub(crate) fn truncate_count_text_for_table_cell(
     }
 }
 
+/// Reads one line (up to and including `\n`) from `reader`, repairing invalid
+/// UTF-8 byte sequences with U+FFFD instead of erroring - the non-metafied
+/// counterpart to [`crate::utils::zsh_line::ZshLineRead::read_zsh_line_lossy`],
+/// used by the plain-text Bash and Fish importers' `--lossy` support. Returns
+/// `None` at EOF, otherwise the decoded line (trailing `\n`/`\r\n` stripped)
+/// and whether a repair happened.
+pub(crate) fn read_line_lossy<R: std::io::BufRead>(
+    reader: &mut R,
+) -> std::io::Result<Option<(String, bool)>> {
+    let mut raw = Vec::new();
+    let read = reader.read_until(b'\n', &mut raw)?;
+    if read == 0 {
+        return Ok(None);
+    }
+
+    if raw.last() == Some(&b'\n') {
+        raw.pop();
+        if raw.last() == Some(&b'\r') {
+            raw.pop();
+        }
+    }
+
+    let repaired = std::str::from_utf8(&raw).is_err();
+    let line = String::from_utf8_lossy(&raw).into_owned();
+
+    Ok(Some((line, repaired)))
+}
+
+/// Renders `text` as a double-quoted JSON string, with proper JSON escaping -
+/// unlike `{:?}` (Rust's `Debug` format), which looks similar but escapes
+/// Unicode control characters as `\u{NNNN}` (braces, variable width), not
+/// JSON's `\u00NN`, and so produces invalid JSON for any command containing
+/// one. Used by [`crate::history::TimeAnalysis::to_json`] and
+/// [`crate::export::write_entries`]'s JSON/NDJSON output instead of pulling
+/// in `serde_json` just for string escaping.
+pub(crate) fn to_json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
 /// Helper function to format ranking with medal icons for top 3.
 pub(crate) fn format_rank_icon(rank: usize) -> String {
     match rank {
@@ -93,6 +113,19 @@ mod tests {
         assert_eq!(truncated, "This is a very long ... (5 times)");
     }
 
+    #[test]
+    fn test_to_json_string_escapes_control_characters() {
+        assert_eq!(to_json_string("plain"), "\"plain\"");
+        assert_eq!(
+            to_json_string("quote \" and backslash \\"),
+            "\"quote \\\" and backslash \\\\\""
+        );
+        assert_eq!(to_json_string("a\nb\tc\rd"), "\"a\\nb\\tc\\rd\"");
+        assert_eq!(to_json_string("bell\u{0007}"), "\"bell\\u0007\"");
+        // Unlike `{:?}`, non-ASCII characters are left as-is, not escaped.
+        assert_eq!(to_json_string("café ☕"), "\"café ☕\"");
+    }
+
     #[test]
     fn test_format_rank_icon() {
         assert_eq!(format_rank_icon(1), "ðŸ¥‡");
@@ -100,4 +133,35 @@ mod tests {
         assert_eq!(format_rank_icon(3), "ðŸ¥‰");
         assert_eq!(format_rank_icon(4), "4");
     }
+
+    #[test]
+    fn test_read_line_lossy_repairs_invalid_utf8() {
+        let mut reader = std::io::Cursor::new(b"ls \xFF\xFElater\n".to_vec());
+        let (line, repaired) = read_line_lossy(&mut reader).unwrap().unwrap();
+
+        assert!(repaired);
+        assert_eq!(line, "ls \u{FFFD}\u{FFFD}later");
+    }
+
+    #[test]
+    fn test_read_line_lossy_leaves_valid_lines_untouched() {
+        let mut reader = std::io::Cursor::new(b"ls -la\r\n".to_vec());
+        let (line, repaired) = read_line_lossy(&mut reader).unwrap().unwrap();
+
+        assert!(!repaired);
+        assert_eq!(line, "ls -la");
+    }
+
+    #[test]
+    fn test_read_line_lossy_returns_none_at_eof() {
+        let mut reader = std::io::Cursor::new(Vec::new());
+        assert!(read_line_lossy(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines(b""), 0);
+        assert_eq!(count_lines(b"one line, no trailing newline"), 0);
+        assert_eq!(count_lines(b"line one\nline two\nline three\n"), 3);
+    }
 }
@@ -1,4 +1,5 @@
-use std::io::BufRead;
+use crate::errors::HistoryError;
+use std::io::{BufRead, BufReader, Read};
 
 struct Guard<'a> {
     buf: &'a mut Vec<u8>,
@@ -62,6 +63,39 @@ pub(crate) trait ZshLineRead: BufRead {
     {
         ZshLines { buf: self }
     }
+
+    /// Like [`Self::read_zsh_line`], but instead of erroring out on invalid
+    /// UTF-8, appends the decoded-but-unmetafied raw bytes to `buf` as-is and
+    /// reports whether they contain invalid UTF-8 ("repaired" in the sense
+    /// that the caller no longer has to abort the whole run over it). Unlike
+    /// `String::from_utf8_lossy`, the invalid bytes themselves are kept
+    /// untouched rather than replaced with U+FFFD, so a later
+    /// [`crate::entry::HistoryEntry::to_raw_history_line`] can write them
+    /// back out unchanged.
+    fn read_zsh_line_lossy(&mut self, buf: &mut Vec<u8>) -> Result<(usize, bool), std::io::Error> {
+        let mut raw = Vec::new();
+        let read = self.read_until(b'\n', &mut raw)?;
+        if read == 0 {
+            return Ok((0, false));
+        }
+
+        let mut unmetafied = Vec::with_capacity(raw.len());
+        let mut src = 0;
+        while src < raw.len() {
+            if raw[src] == 0x83 && src + 1 < raw.len() {
+                unmetafied.push(raw[src + 1] ^ 0x20);
+                src += 2;
+            } else {
+                unmetafied.push(raw[src]);
+                src += 1;
+            }
+        }
+
+        let repaired = std::str::from_utf8(&unmetafied).is_err();
+        buf.extend_from_slice(&unmetafied);
+
+        Ok((read, repaired))
+    }
 }
 
 impl<B: BufRead + ?Sized> ZshLineRead for B {}
@@ -90,3 +124,260 @@ impl<B: BufRead> Iterator for ZshLines<B> {
         }
     }
 }
+
+enum Lines<R> {
+    /// Routes through [`ZshLineRead::zsh_lines`] so strict-mode decoding stays
+    /// consistent with every other caller of that trait.
+    Strict(ZshLines<BufReader<R>>),
+    Lossy(BufReader<R>),
+}
+
+/// Streams complete (possibly multi-line) Zsh history commands out of any
+/// `R: Read`, decoding metafied bytes via [`ZshLineRead`] one line at a time
+/// instead of buffering the whole file first. This lets callers like
+/// `analyze` or duplicate detection work on multi-hundred-MB histories
+/// without holding every command in memory at once.
+pub(crate) struct ZshCommands<R> {
+    lines: Lines<R>,
+    line_number: usize,
+}
+
+impl<R: Read> ZshCommands<R> {
+    pub(crate) fn new(reader: R, lossy: bool) -> Self {
+        let lines = if lossy {
+            Lines::Lossy(BufReader::new(reader))
+        } else {
+            Lines::Strict(BufReader::new(reader).zsh_lines())
+        };
+
+        Self {
+            lines,
+            line_number: 0,
+        }
+    }
+}
+
+impl<R: Read> Iterator for ZshCommands<R> {
+    /// The assembled command's raw (unmetafied) bytes - preserved exactly
+    /// even when they aren't valid UTF-8, see
+    /// [`crate::entry::HistoryEntry::try_from_raw_bytes`] - and whether any
+    /// of its lines had invalid UTF-8 (always `false` when not built in lossy
+    /// mode; in lossy mode this no longer means the bytes were altered, just
+    /// that they're worth flagging to the user).
+    type Item = Result<(Vec<u8>, bool), HistoryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut current_command: Vec<u8> = Vec::new();
+        let mut repaired_any = false;
+
+        loop {
+            self.line_number += 1;
+
+            let (read, mut raw) = match &mut self.lines {
+                Lines::Strict(lines) => match lines.next() {
+                    None => (0, Vec::new()),
+                    Some(Ok(mut line)) => {
+                        // `ZshLines` already strips the trailing newline; put a
+                        // sentinel back on so the logic below stays identical
+                        // between the two modes.
+                        line.push('\n');
+                        (1, line.into_bytes())
+                    }
+                    Some(Err(e)) => {
+                        return Some(Err(HistoryError::LineEncodingError(
+                            self.line_number.to_string(),
+                            e.to_string(),
+                        )));
+                    }
+                },
+                Lines::Lossy(reader) => {
+                    let mut raw = Vec::new();
+                    match reader.read_zsh_line_lossy(&mut raw) {
+                        Ok((read, repaired)) => {
+                            repaired_any |= repaired;
+                            (read, raw)
+                        }
+                        Err(e) => {
+                            return Some(Err(HistoryError::LineEncodingError(
+                                self.line_number.to_string(),
+                                e.to_string(),
+                            )));
+                        }
+                    }
+                }
+            };
+
+            if read == 0 {
+                return if current_command.is_empty() {
+                    None
+                } else {
+                    Some(Ok((current_command, repaired_any)))
+                };
+            }
+
+            if raw.last() == Some(&b'\n') {
+                raw.pop();
+                if raw.last() == Some(&b'\r') {
+                    raw.pop();
+                }
+            }
+
+            if raw.last() == Some(&b'\\') {
+                current_command.extend_from_slice(&raw);
+            } else {
+                if !current_command.is_empty() {
+                    current_command.push(b'\n');
+                }
+                current_command.extend_from_slice(&raw);
+                return Some(Ok((current_command, repaired_any)));
+            }
+        }
+    }
+}
+
+/// Encodes bytes into Zsh's metafied history format: the inverse of
+/// [`ZshLineRead::read_zsh_line`]'s decoding. Every byte that's NUL (`0x00`),
+/// equal to `0x83` (Zsh's own meta marker), or with its high bit set is
+/// replaced by the two-byte sequence `0x83, byte ^ 0x20` - the same set zsh
+/// itself treats as "meta" (its `imeta()` check) - so commands containing
+/// those bytes survive being written back to `$HISTFILE` unchanged.
+pub(crate) fn metafy(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    for &byte in bytes {
+        if byte == 0x00 || byte == 0x83 || byte & 0x80 != 0 {
+            out.push(0x83);
+            out.push(byte ^ 0x20);
+        } else {
+            out.push(byte);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metafy_round_trips_through_read_zsh_line() {
+        // A grab-bag of strings exercising multi-byte UTF-8 (so every byte of the
+        // encoded command is exactly what `metafy` has to round-trip), plus zsh's
+        // own meta marker appearing verbatim in the text.
+        let samples = [
+            "plain ascii command",
+            "café ☕ --flag",
+            "echo '日本語 テスト'",
+            "printf '\u{0083}literal meta marker'",
+            "emoji party 🎉🎉🎉",
+        ];
+
+        for sample in samples {
+            let mut encoded = metafy(sample.as_bytes());
+            encoded.push(b'\n');
+
+            let mut cursor = std::io::Cursor::new(encoded);
+            let mut decoded = String::new();
+            cursor.read_zsh_line(&mut decoded).unwrap();
+            if decoded.ends_with('\n') {
+                decoded.pop();
+            }
+
+            assert_eq!(
+                decoded, sample,
+                "metafy must be the exact inverse of read_zsh_line's decoding"
+            );
+        }
+    }
+
+    // The direction that actually matters for `History::write`: a file read
+    // with `read_zsh_line` and immediately rewritten with `metafy` must be
+    // byte-identical to what was on disk, not just "decode what we ourselves
+    // just encoded" (covered by the test above). Also exercises the NUL byte,
+    // which zsh metafies like any other high/meta byte.
+    #[test]
+    fn test_read_then_rewrite_round_trips_raw_metafied_bytes() {
+        let samples = [
+            "plain ascii command",
+            "café ☕ --flag",
+            "printf '\u{0083}literal meta marker'",
+            "a null \0 byte",
+        ];
+
+        for sample in samples {
+            let mut raw = metafy(sample.as_bytes());
+            raw.push(b'\n');
+
+            let mut cursor = std::io::Cursor::new(raw.clone());
+            let mut decoded = String::new();
+            cursor.read_zsh_line(&mut decoded).unwrap();
+            if decoded.ends_with('\n') {
+                decoded.pop();
+            }
+
+            let mut rewritten = metafy(decoded.as_bytes());
+            rewritten.push(b'\n');
+
+            assert_eq!(
+                rewritten, raw,
+                "a file read then rewritten with no edits must be byte-identical"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_zsh_line_lossy_flags_but_preserves_invalid_utf8() {
+        let mut raw = b": 1732577005:0;echo 'invalid \xFF\xFE command'".to_vec();
+        raw.push(b'\n');
+
+        let mut cursor = std::io::Cursor::new(raw.clone());
+        let mut decoded = Vec::new();
+        let (read, repaired) = cursor.read_zsh_line_lossy(&mut decoded).unwrap();
+
+        assert!(read > 0);
+        assert!(repaired, "invalid UTF-8 sequences should be flagged");
+        // The raw bytes are preserved exactly, not replaced with U+FFFD.
+        assert_eq!(decoded, raw[..raw.len() - 1]);
+    }
+
+    #[test]
+    fn test_read_zsh_line_lossy_is_a_no_op_on_valid_utf8() {
+        let mut raw = "echo 'café'".as_bytes().to_vec();
+        raw.push(b'\n');
+
+        let mut cursor = std::io::Cursor::new(raw);
+        let mut decoded = Vec::new();
+        let (_read, repaired) = cursor.read_zsh_line_lossy(&mut decoded).unwrap();
+
+        assert!(!repaired);
+        assert_eq!(decoded, "echo 'café'\n".as_bytes());
+    }
+
+    #[test]
+    fn test_zsh_commands_reassembles_multiline_commands() {
+        let raw = ": 1732577005:0;echo hello\n: 1732577037:0;echo multi\\\nline\n";
+        let commands: Vec<_> = ZshCommands::new(std::io::Cursor::new(raw), false)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            commands,
+            vec![
+                (b": 1732577005:0;echo hello".to_vec(), false),
+                (b": 1732577037:0;echo multi\\\nline".to_vec(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zsh_commands_lossy_preserves_invalid_utf8_bytes() {
+        let mut raw = b": 1732577005:0;echo \xFF\xFE".to_vec();
+        raw.push(b'\n');
+
+        let mut commands = ZshCommands::new(std::io::Cursor::new(raw), true);
+        let (command, repaired) = commands.next().unwrap().unwrap();
+
+        assert!(repaired);
+        assert_eq!(command, b": 1732577005:0;echo \xFF\xFE");
+        assert!(commands.next().is_none());
+    }
+}